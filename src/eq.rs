@@ -0,0 +1,174 @@
+//! Multilinear equality-polynomial ("eq") table evaluation.
+//!
+//! For a point `r = (r_0, ..., r_{n-1})` and a scalar `c`, entry `i` of the
+//! length-`2^n` table is `c * prod_j (bit_j(i) ? r_j : (1 - r_j))`, i.e. `c`
+//! scaled by the equality polynomial `eq(i, r)` evaluated at the boolean
+//! hypercube point corresponding to the bits of `i` (bit `j` is the `j`-th
+//! most significant bit, matching `verify::reference`).
+//!
+//! Two independent serial backends are provided so results can be
+//! cross-checked: [`eval_eq`] is the recursive divide-and-conquer backend
+//! (the promoted `recurse_mul_sub` pattern), and [`eval_eq_doubling`] is a
+//! non-recursive backend that builds the table up one variable at a time.
+//! The doubling backend avoids the deep recursion that the harness in
+//! `main.rs` shows triggering a miscompilation, and tends to be more
+//! cache-friendly. [`eval_eq_parallel`] builds on the recursive split to
+//! fan the table out across Rayon for large dimensions.
+
+use std::hint::black_box;
+
+use ark_ff::Field;
+use rayon::prelude::*;
+
+/// Optimization-barrier strategy for the eq-table backends, letting callers
+/// who hit the LLVM miscompilation that motivated this module select the
+/// minimal `black_box` placement that restores correctness, rather than
+/// having to fall back to building the whole crate at `-O0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Barrier {
+    /// No barrier; full optimization.
+    #[default]
+    None,
+    /// `black_box` around the `scalar * x0` product only.
+    PerMul,
+    /// `black_box` around the `scalar * x0` product, and again around the
+    /// resulting `s0`/`s1` pair at each recursion/doubling boundary.
+    PerVariable,
+}
+
+impl Barrier {
+    fn guard_mul<F>(self, product: F) -> F {
+        match self {
+            Barrier::PerMul | Barrier::PerVariable => black_box(product),
+            Barrier::None => product,
+        }
+    }
+
+    fn guard_step<F>(self, value: F) -> F {
+        match self {
+            Barrier::PerVariable => black_box(value),
+            Barrier::None | Barrier::PerMul => value,
+        }
+    }
+}
+
+/// Materializes `eq(·, point) * scalar` into `out`, accumulating
+/// (`out[i] += scalar * eq(i, point)`).
+///
+/// `out` must have length `2^point.len()`. Uses the recursive backend.
+pub fn eval_eq<F: Field>(point: &[F], scalar: F, out: &mut [F]) {
+    eval_eq_with_barrier(point, scalar, out, Barrier::None);
+}
+
+/// As [`eval_eq`], with a [`Barrier`] strategy applied around the
+/// `scalar * x0` product and/or each recursion boundary.
+pub fn eval_eq_with_barrier<F: Field>(point: &[F], scalar: F, out: &mut [F], barrier: Barrier) {
+    assert_eq!(out.len(), 1 << point.len());
+    eval_eq_recursive(out, point, scalar, barrier);
+}
+
+/// Recursive backend: splits `acc` in half at each variable, sending
+/// `scalar * (1 - x0)` to the low half and `scalar * x0` to the high half.
+///
+/// `#[inline(never)]`: this is the literal promotion of the original
+/// `recurse_mul_sub` probe in `main.rs`, and that attribute is what lets the
+/// probe reliably reproduce the deep-recursion-triggered LLVM miscompile
+/// this crate exists to chase; an inlined, monomorphized copy may not
+/// reproduce it.
+#[inline(never)]
+pub(crate) fn eval_eq_recursive<F: Field>(acc: &mut [F], point: &[F], scalar: F, barrier: Barrier) {
+    if let [x0, xs @ ..] = point {
+        let (a0, a1) = acc.split_at_mut(1 << xs.len());
+        let s1 = barrier.guard_step(barrier.guard_mul(scalar * x0));
+        let s0 = barrier.guard_step(scalar - s1);
+        eval_eq_recursive(a0, xs, s0, barrier);
+        eval_eq_recursive(a1, xs, s1, barrier);
+    } else {
+        acc[0] += scalar;
+    }
+}
+
+/// Non-recursive "doubling" backend: starts a zeroed scratch table with
+/// `scratch[0] = scalar` and, for each variable (processed from last to first
+/// so the bit ordering matches the recursive backend), doubles the filled
+/// prefix by splitting off the high half as `scratch[k] * r` and subtracting
+/// that back from the low half; the finished table is then added into `out`.
+pub fn eval_eq_doubling<F: Field>(point: &[F], scalar: F, out: &mut [F]) {
+    eval_eq_doubling_with_barrier(point, scalar, out, Barrier::None);
+}
+
+/// As [`eval_eq_doubling`], with a [`Barrier`] strategy applied around the
+/// `scratch[k] * r` product and/or each doubling boundary.
+///
+/// `#[inline(never)]` for the same reason as [`eval_eq_recursive`]: an
+/// inlined, monomorphized copy may not reproduce the LLVM miscompile this
+/// crate exists to chase.
+#[inline(never)]
+pub fn eval_eq_doubling_with_barrier<F: Field>(point: &[F], scalar: F, out: &mut [F], barrier: Barrier) {
+    assert_eq!(out.len(), 1 << point.len());
+    // Build the new contribution in a zeroed scratch buffer rather than
+    // doubling in place: `out` may already hold unrelated accumulated
+    // content (per the `out[i] += scalar * eq(i, point)` contract), and
+    // reading that content back into the `*l * r` / `*l - hi_val` split
+    // would mix it into entries it has no business touching.
+    let mut scratch = vec![F::ZERO; out.len()];
+    scratch[0] += scalar;
+    let mut len = 1;
+    for r in point.iter().rev() {
+        let (lo, hi) = scratch.split_at_mut(len);
+        for (l, h) in lo.iter_mut().zip(hi.iter_mut()) {
+            let hi_val = barrier.guard_step(barrier.guard_mul(*l * r));
+            let lo_val = barrier.guard_step(*l - hi_val);
+            *h += hi_val;
+            *l = lo_val;
+        }
+        len *= 2;
+    }
+    for (o, s) in out.iter_mut().zip(scratch) {
+        *o += s;
+    }
+}
+
+/// Allocating convenience wrapper around [`eval_eq`].
+pub fn eval_eq_vec<F: Field>(point: &[F], scalar: F) -> Vec<F> {
+    let mut out = vec![F::ZERO; 1 << point.len()];
+    eval_eq(point, scalar, &mut out);
+    out
+}
+
+/// Variable-count threshold at or below which [`eval_eq_parallel`] falls back to
+/// the serial [`eval_eq_doubling`] backend rather than paying Rayon's task
+/// overhead on a table too small to benefit from it.
+pub const PARALLEL_THRESHOLD_VARS: usize = 10;
+
+/// Parallel backend for large dimensions: splits the hypercube on the
+/// leading variables of `point` into independent sub-cubes, each seeded
+/// with its own scalar (the `s0`/`s1` products of the recursive split,
+/// computed once via the doubling backend), and fills the disjoint `out`
+/// slices concurrently with Rayon. The sub-cube slices are provably
+/// disjoint because `out.par_chunks_mut(chunk_len)` partitions a single
+/// contiguous buffer into non-overlapping, equal-sized chunks, so no
+/// locking is needed.
+///
+/// The seeds are computed with [`eval_eq_doubling`] rather than [`eval_eq`]
+/// so that the whole parallel path stays free of the deep recursion this
+/// module exists to offer an alternative to.
+///
+/// Falls back to [`eval_eq_doubling`] when `point.len() <=
+/// PARALLEL_THRESHOLD_VARS`.
+pub fn eval_eq_parallel<F: Field>(point: &[F], scalar: F, out: &mut [F]) {
+    assert_eq!(out.len(), 1 << point.len());
+    if point.len() <= PARALLEL_THRESHOLD_VARS {
+        eval_eq_doubling(point, scalar, out);
+        return;
+    }
+
+    let k = point.len() - PARALLEL_THRESHOLD_VARS;
+    let (head, tail) = point.split_at(k);
+    let mut seeds = vec![F::ZERO; 1 << head.len()];
+    eval_eq_doubling(head, scalar, &mut seeds);
+    let chunk_len = 1 << tail.len();
+    out.par_chunks_mut(chunk_len)
+        .zip(seeds.par_iter())
+        .for_each(|(chunk, &seed)| eval_eq_doubling(tail, seed, chunk));
+}
@@ -3,6 +3,9 @@
 use ark_ff::{AdditiveGroup, Field, Fp256, MontBackend, MontConfig};
 use std::hint::black_box;
 
+mod eq;
+mod verify;
+
 #[derive(MontConfig)]
 #[modulus = "21888242871839275222246405745257275088548364400416034343698204186575808495617"]
 #[generator = "5"]
@@ -73,28 +76,6 @@ fn recurse_mul_sub<F: Field>(acc: &mut [F], point: &[F], scalar: F) {
     }
 }
 
-// ============================================================================
-// Reference implementation
-// ============================================================================
-
-#[inline(never)]
-fn reference<F: Field>(acc: &mut [F], point: &[F], scalar: F) {
-    let n = 1 << point.len();
-    for i in 0..n {
-        let mut contribution = scalar;
-        for (j, &pj) in point.iter().enumerate() {
-            let bit = (i >> (point.len() - 1 - j)) & 1;
-            if bit == 1 {
-                contribution = contribution * pj;
-            } else {
-                contribution = contribution * (F::ONE - pj);
-            }
-        }
-        black_box(&contribution);
-        acc[i] += contribution;
-    }
-}
-
 fn gen_vec(size: usize, seed: u64) -> Vec<F> {
     let base = F::from(seed);
     let mut current = base;
@@ -172,11 +153,118 @@ fn main() {
         let mut acc1 = vec![F::ZERO; size];
         let mut acc2 = vec![F::ZERO; size];
         recurse_mul_sub(&mut acc1, &point, scalar);
-        reference(&mut acc2, &point, scalar);
+        verify::reference(&point, scalar, &mut acc2);
         if acc1 != acc2 { fails += 1; }
     }
     println!("Recursive MUL then SUB:          {} ({}/{})", if fails > 0 { "FAIL" } else { "PASS" }, fails, ITERS);
 
+    // Test 5: the promoted eq-table API, recursive vs. doubling vs.
+    // reference, via the reusable differential harness.
+    let failures = verify::fuzz::<F>(ITERS, DIM, 5000);
+    println!("verify::fuzz (recursive vs doubling vs reference): {} ({}/{})",
+        if failures.is_empty() { "PASS" } else { "FAIL" }, failures.len(), ITERS);
+    if let Some(first) = failures.first() {
+        println!("  first divergence: case {}, index {}, variable {}",
+            first.case, first.divergence.index, first.divergence.variable);
+    }
+
+    // Test 5b: eval_eq vs. eval_eq_doubling when `out` is already carrying
+    // unrelated content, i.e. accumulation (`out[i] += ...`) rather than the
+    // zeroed-`out` case every other test above exercises.
+    let mut fails_accum = 0;
+    for i in 0..ITERS {
+        let point: Vec<F> = gen_vec(DIM, i as u64 + 5);
+        let scalar = F::from(i as u64 + 5000);
+        let seed: Vec<F> = gen_vec(size, i as u64 + 5500);
+        let mut acc_recursive = seed.clone();
+        let mut acc_doubling = seed;
+        eq::eval_eq(&point, scalar, &mut acc_recursive);
+        eq::eval_eq_doubling(&point, scalar, &mut acc_doubling);
+        if acc_recursive != acc_doubling { fails_accum += 1; }
+    }
+    println!("eval_eq vs eval_eq_doubling (seeded out): {} ({}/{})",
+        if fails_accum > 0 { "FAIL" } else { "PASS" }, fails_accum, ITERS);
+
+    // Test 5c: eval_eq_vec, the allocating entry point promoted alongside
+    // eval_eq, against the same reference Test 5 cross-checks eval_eq with.
+    let mut fails_vec = 0;
+    for i in 0..ITERS {
+        let point: Vec<F> = gen_vec(DIM, i as u64 + 55);
+        let scalar = F::from(i as u64 + 5550);
+        let mut acc_ref = vec![F::ZERO; size];
+        verify::reference(&point, scalar, &mut acc_ref);
+        let acc_vec = eq::eval_eq_vec(&point, scalar);
+        if acc_vec != acc_ref { fails_vec += 1; }
+    }
+    println!("eval_eq_vec vs reference:         {} ({}/{})",
+        if fails_vec > 0 { "FAIL" } else { "PASS" }, fails_vec, ITERS);
+
+    // Test 6: eval_eq_parallel against the same reference (DIM equals the
+    // parallel threshold here, so this also exercises the serial fallback).
+    let mut fails_parallel = 0;
+    for i in 0..ITERS {
+        let point: Vec<F> = gen_vec(DIM, i as u64 + 6);
+        let scalar = F::from(i as u64 + 6000);
+        let mut acc_ref = vec![F::ZERO; size];
+        verify::reference(&point, scalar, &mut acc_ref);
+
+        let mut acc_parallel = vec![F::ZERO; size];
+        eq::eval_eq_parallel(&point, scalar, &mut acc_parallel);
+        if acc_parallel != acc_ref { fails_parallel += 1; }
+    }
+    println!("eval_eq_parallel:                {} ({}/{})", if fails_parallel > 0 { "FAIL" } else { "PASS" }, fails_parallel, ITERS);
+
+    // Test 6b: eval_eq_parallel above PARALLEL_THRESHOLD_VARS, so this
+    // actually exercises the sub-cube split/seed path rather than always
+    // falling back to the serial backend like Test 6 does at DIM = 10.
+    const ITERS_LARGE: usize = 10;
+    const DIM_LARGE: usize = eq::PARALLEL_THRESHOLD_VARS + 3;
+    let size_large = 1 << DIM_LARGE;
+    let mut fails_parallel_large = 0;
+    for i in 0..ITERS_LARGE {
+        let point: Vec<F> = gen_vec(DIM_LARGE, i as u64 + 60);
+        let scalar = F::from(i as u64 + 60000);
+        let mut acc_ref = vec![F::ZERO; size_large];
+        verify::reference(&point, scalar, &mut acc_ref);
+
+        let mut acc_parallel = vec![F::ZERO; size_large];
+        eq::eval_eq_parallel(&point, scalar, &mut acc_parallel);
+        if acc_parallel != acc_ref { fails_parallel_large += 1; }
+    }
+    println!("eval_eq_parallel (dim {} > threshold): {} ({}/{})", DIM_LARGE,
+        if fails_parallel_large > 0 { "FAIL" } else { "PASS" }, fails_parallel_large, ITERS_LARGE);
+
+    // Test 7: cost AND correctness of each barrier strategy. `Barrier`'s
+    // whole purpose is restoring correctness under the miscompile, so every
+    // variant (not just the timing of `None`) needs to be checked against
+    // `reference`, or a regression in `PerMul`/`PerVariable` ships silent.
+    println!();
+    println!("Barrier strategy cost + correctness (eval_eq_with_barrier, {} iters):", ITERS);
+    for barrier in [eq::Barrier::None, eq::Barrier::PerMul, eq::Barrier::PerVariable] {
+        let mut fails_barrier = 0;
+        let mut fails_barrier_doubling = 0;
+        let start = std::time::Instant::now();
+        for i in 0..ITERS {
+            let point: Vec<F> = gen_vec(DIM, i as u64 + 7);
+            let scalar = F::from(i as u64 + 7000);
+            let mut acc = vec![F::ZERO; size];
+            eq::eval_eq_with_barrier(&point, scalar, &mut acc, barrier);
+            black_box(&acc);
+
+            let mut acc_doubling = vec![F::ZERO; size];
+            eq::eval_eq_doubling_with_barrier(&point, scalar, &mut acc_doubling, barrier);
+            black_box(&acc_doubling);
+
+            let mut acc_ref = vec![F::ZERO; size];
+            verify::reference(&point, scalar, &mut acc_ref);
+            if acc != acc_ref { fails_barrier += 1; }
+            if acc_doubling != acc_ref { fails_barrier_doubling += 1; }
+        }
+        println!("  {:?}: {:?} (recursive {} ({}/{}), doubling {} ({}/{}))", barrier, start.elapsed(),
+            if fails_barrier > 0 { "FAIL" } else { "PASS" }, fails_barrier, ITERS,
+            if fails_barrier_doubling > 0 { "FAIL" } else { "PASS" }, fails_barrier_doubling, ITERS);
+    }
+
     println!();
     println!("If only \"MUL then SUB\" fails, the bug is in the");
     println!("combination: s1 = scalar * x0; s0 = scalar - s1");
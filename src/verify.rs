@@ -0,0 +1,160 @@
+//! Differential / property-testing harness for the eq-table backends.
+//!
+//! Generalizes the hand-rolled PASS/FAIL loop in `main.rs` into a reusable
+//! driver: for random `(point, scalar)` cases, it runs the recursive
+//! ([`crate::eq::eval_eq`]), doubling ([`crate::eq::eval_eq_doubling`]) and
+//! triple-loop [`reference`] implementations and checks that all three agree
+//! element-wise, returning each disagreement rather than panicking. `main.rs` calls [`reference`] directly for its own
+//! hand-rolled tests rather than keeping a second copy of the oracle, so
+//! there is exactly one triple-loop implementation to keep in sync.
+//!
+//! Everything here is generic over `F: Field`, so the same suite can be run
+//! against `ark_ff` Montgomery backends of different limb counts to help
+//! confirm whether a given miscompile is modulus/limb-specific.
+//!
+//! [`Divergence::variable`] is recovered by binary search over the `point`
+//! prefix length: shrinking `point` and re-running all three implementations
+//! at each length finds the smallest prefix that still disagrees, which pins
+//! down the variable whose introduction caused the divergence.
+
+use std::hint::black_box;
+
+use ark_ff::Field;
+
+use crate::eq::{eval_eq_doubling_with_barrier, eval_eq_recursive, Barrier};
+
+/// Deterministic (non-cryptographic) pseudo-random field-element stream,
+/// seeded by `seed`. Mirrors `gen_vec` in `main.rs`, kept local here so the
+/// harness has no dependency on an external `rand` crate.
+fn gen_vec<F: Field>(size: usize, seed: u64) -> Vec<F> {
+    let base = F::from(seed);
+    let mut current = base;
+    (0..size)
+        .map(|_| {
+            let v = current;
+            current = current * base + F::ONE;
+            v
+        })
+        .collect()
+}
+
+/// Triple-loop reference implementation, generic over `F`, accumulating into
+/// `out` (`#[inline(never)]`: the oracle needs protection against being
+/// inlined/monomorphized into a shape that stops reproducing the miscompile).
+#[inline(never)]
+pub fn reference<F: Field>(point: &[F], scalar: F, out: &mut [F]) {
+    let n = 1 << point.len();
+    for (i, entry) in out.iter_mut().enumerate().take(n) {
+        let mut contribution = scalar;
+        for (j, &pj) in point.iter().enumerate() {
+            let bit = (i >> (point.len() - 1 - j)) & 1;
+            contribution = if bit == 1 { contribution * pj } else { contribution * (F::ONE - pj) };
+        }
+        black_box(&contribution);
+        *entry += contribution;
+    }
+}
+
+/// Where the recursive backend, the doubling backend and [`reference`] first
+/// disagreed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    /// First hypercube index at which the three output tables do not all
+    /// agree.
+    pub index: usize,
+    /// First variable (0-based, in `point` order) whose introduction causes
+    /// the backends to disagree, recovered by [`localize_variable`] via
+    /// binary search over the `point` prefix length (see the module docs).
+    pub variable: usize,
+}
+
+/// Outcome of one differential-testing round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Agree,
+    Disagree(Divergence),
+}
+
+/// Runs the recursive and doubling backends plus [`reference`] on
+/// `(point, scalar)` and reports the first hypercube index at which they
+/// disagree, or `None` if all three agree.
+fn first_divergent_index<F: Field>(point: &[F], scalar: F) -> Option<usize> {
+    let size = 1 << point.len();
+
+    let mut acc_ref = vec![F::ZERO; size];
+    reference(point, scalar, &mut acc_ref);
+
+    let mut acc_recursive = vec![F::ZERO; size];
+    eval_eq_recursive(&mut acc_recursive, point, scalar, Barrier::None);
+
+    let mut acc_doubling = vec![F::ZERO; size];
+    eval_eq_doubling_with_barrier(point, scalar, &mut acc_doubling, Barrier::None);
+
+    acc_recursive
+        .iter()
+        .zip(acc_doubling.iter())
+        .zip(acc_ref.iter())
+        .position(|((r, d), f)| r != d || r != f)
+}
+
+/// Binary searches over the `point` prefix length for the variable whose
+/// introduction first causes the three implementations to disagree.
+///
+/// The empty prefix (`point[..0]`) trivially agrees (all three reduce to
+/// writing `scalar` into a length-1 table), and `point` itself is assumed to
+/// already disagree (callers only reach this once [`first_divergent_index`]
+/// found one); binary search over that range for the shortest still-disagreeing
+/// prefix pins down the variable at which the divergence was introduced.
+fn localize_variable<F: Field>(point: &[F], scalar: F) -> usize {
+    if point.is_empty() {
+        return 0;
+    }
+    let mut lo = 0;
+    let mut hi = point.len();
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if first_divergent_index(&point[..mid], scalar).is_some() {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi - 1
+}
+
+/// Runs the recursive and doubling backends plus [`reference`] on the same
+/// `(point, scalar)` input and cross-checks all three.
+pub fn run_case<F: Field>(point: &[F], scalar: F) -> Verdict {
+    let Some(index) = first_divergent_index(point, scalar) else { return Verdict::Agree };
+
+    let variable = localize_variable(point, scalar);
+    Verdict::Disagree(Divergence { index, variable })
+}
+
+/// One fuzz case that disagreed: its index in the sweep and the resulting
+/// [`Divergence`].
+pub struct Failure {
+    pub case: usize,
+    pub divergence: Divergence,
+}
+
+/// Runs `iters` random `(point, scalar)` cases of dimension `dim`, seeded
+/// from `seed_base`, and collects every disagreement found.
+///
+/// Build the same call under different `cargo build` / `cargo build
+/// --release` profiles (and, if desired, different `RUSTFLAGS` codegen
+/// settings) to sweep optimization levels, and instantiate with different
+/// `ark_ff` `Fp*<MontBackend<_, N>>` types to sweep field backends.
+pub fn fuzz<F: Field>(iters: usize, dim: usize, seed_base: u64) -> Vec<Failure> {
+    (0..iters)
+        .filter_map(|case| {
+            let seed = seed_base.wrapping_add(case as u64);
+            let point: Vec<F> = gen_vec(dim, seed);
+            let scalar = F::from(seed.wrapping_add(1));
+            match run_case(&point, scalar) {
+                Verdict::Agree => None,
+                Verdict::Disagree(divergence) => Some(Failure { case, divergence }),
+            }
+        })
+        .collect()
+}